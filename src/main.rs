@@ -1,18 +1,22 @@
 // src/main.rs
 use anyhow::{Context, Result};
 use futures::{SinkExt, StreamExt};
-use log::{error, info};
+use log::{error, info, warn};
 use serde::Deserialize;
 use serialport::SerialPort;
 use std::{
     collections::HashMap, io::{Read, Write}, sync::Arc, time::Duration
 };
 use tokio::{
-    net::TcpListener,
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, UnixListener},
     signal,
-    sync::{mpsc, Mutex},
+    sync::{broadcast, mpsc, Mutex},
+    task::{JoinHandle, JoinSet},
 };
+use tokio_rustls::{rustls, TlsAcceptor};
 use tokio_tungstenite::tungstenite::{Message, http};
+use tokio_util::sync::CancellationToken;
 
 // Helper functions that provide defaults for the config fields.
 fn default_bind_address() -> String {
@@ -21,6 +25,9 @@ fn default_bind_address() -> String {
 fn default_bind_port() -> u16 {
     9001
 }
+fn default_drain_timeout_secs() -> u64 {
+    30
+}
 
 /// Configuration file format (YAML)
 #[derive(Debug, Deserialize)]
@@ -31,6 +38,32 @@ struct Config {
     bind_port: u16,
     /// Mapping from a WebSocket endpoint name (used in the URL path) to a serial port description.
     endpoints: HashMap<String, SerialConfig>,
+    /// Optional TLS termination. When present, the gateway serves `wss://` instead of `ws://`.
+    tls: Option<TlsConfig>,
+    /// Optional path to a Unix domain socket to listen on, in addition to the TCP listener.
+    unix_socket: Option<String>,
+    /// Outbound WebSocket client endpoints: instead of listening, the gateway dials out to
+    /// a remote server and bridges the connection to a local serial port. Useful for devices
+    /// behind NAT/firewalls that cannot accept inbound connections.
+    #[serde(default)]
+    outbound: Vec<OutboundConfig>,
+    /// How long, in seconds, to wait on shutdown for in-flight connections to finish on their
+    /// own; whatever is left is then asked to close and given the same time again before
+    /// being aborted.
+    #[serde(default = "default_drain_timeout_secs")]
+    shutdown_drain_timeout_secs: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct OutboundConfig {
+    /// Name used for logging.
+    name: String,
+    /// Remote WebSocket URL to dial out to, e.g. `wss://example.com/mydevice`.
+    url: String,
+    /// OS device name, e.g. `/dev/ttyUSB0` or `COM3`
+    port: String,
+    /// Baud rate, e.g. 115200
+    baud_rate: u32,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -39,6 +72,334 @@ struct SerialConfig {
     port: String,
     /// Baud rate, e.g. 115200
     baud_rate: u32,
+    /// Whether this endpoint is served as a WebSocket path or a dedicated raw TCP port.
+    #[serde(default)]
+    mode: ConnectionMode,
+    /// Listen port for `raw_tcp` mode. Required when `mode` is `raw_tcp`, since a raw TCP
+    /// connection has no URL path to select an endpoint by.
+    #[serde(default)]
+    raw_tcp_port: Option<u16>,
+    /// How the serial port behaves when more than one client connects concurrently.
+    #[serde(default)]
+    sharing: SharingMode,
+}
+
+/// How a serial endpoint is exposed to clients.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+enum ConnectionMode {
+    #[default]
+    WebSocket,
+    RawTcp,
+}
+
+/// How a `SerialHub` handles a second concurrent client for the same endpoint.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+enum SharingMode {
+    /// Broadcast serial data to every connected client, and forward every client's writes.
+    #[default]
+    FanOut,
+    /// Reject a second client while one is already connected.
+    Exclusive,
+}
+
+/// Open a serial port by device name and baud rate (blocking call, run in a dedicated thread).
+async fn open_serial_port(port: &str, baud_rate: u32) -> Result<Box<dyn SerialPort>> {
+    let port = port.to_string();
+    tokio::task::spawn_blocking(move || {
+        serialport::new(&port, baud_rate)
+            .timeout(Duration::from_secs(10))
+            .open()
+            .with_context(|| format!("Failed to open serial port {}", &port))
+    })
+    .await?
+}
+
+/// Multiplexes a single serial port across multiple concurrent clients for one endpoint.
+/// The port is opened lazily on the first client and closed again once the last one
+/// disconnects, so clients no longer fight each other to open the device.
+struct SerialHub {
+    name: String,
+    serial_cfg: SerialConfig,
+    state: Mutex<HubState>,
+}
+
+#[derive(Default)]
+struct HubState {
+    clients: usize,
+    inner: Option<HubInner>,
+}
+
+struct HubInner {
+    to_serial_tx: mpsc::UnboundedSender<Vec<u8>>,
+    from_serial_tx: broadcast::Sender<Vec<u8>>,
+    serial_port: Arc<Mutex<Box<dyn SerialPort>>>,
+    reader_task: JoinHandle<()>,
+    writer_task: JoinHandle<()>,
+}
+
+/// A single client's registration with a `SerialHub`: a sender for outgoing bytes and a
+/// broadcast receiver for incoming bytes. Call `release()` when done with it so the hub's
+/// refcount decrement happens before the caller proceeds; dropping it without releasing
+/// first only spawns a best-effort release with no ordering guarantee, and is meant as a
+/// safety net for abrupt drops, not the primary teardown path.
+struct HubClient {
+    hub: Arc<SerialHub>,
+    to_serial_tx: mpsc::UnboundedSender<Vec<u8>>,
+    from_serial_rx: broadcast::Receiver<Vec<u8>>,
+    released: bool,
+}
+
+impl HubClient {
+    /// Release this client's slot on the hub and wait for the refcount decrement (and, if
+    /// this was the last client, the serial port close) to complete.
+    async fn release(mut self) {
+        self.released = true;
+        self.hub.clone().release().await;
+    }
+}
+
+impl SerialHub {
+    fn new(name: String, serial_cfg: SerialConfig) -> Arc<Self> {
+        Arc::new(Self {
+            name,
+            serial_cfg,
+            state: Mutex::new(HubState::default()),
+        })
+    }
+
+    /// Register a new client, opening the serial port if this is the first one. Returns an
+    /// error if the endpoint is `exclusive` and already has a connected client.
+    async fn subscribe(self: &Arc<Self>) -> Result<HubClient> {
+        let mut state = self.state.lock().await;
+        if self.serial_cfg.sharing == SharingMode::Exclusive && state.clients > 0 {
+            anyhow::bail!(
+                "Endpoint `{}` is exclusive and already has a connected client",
+                self.name
+            );
+        }
+
+        if state.inner.is_none() {
+            let serial_port = open_serial_port(&self.serial_cfg.port, self.serial_cfg.baud_rate).await?;
+            info!(
+                "Serial port `{}` opened at {} baud for endpoint `{}`",
+                self.serial_cfg.port, self.serial_cfg.baud_rate, self.name
+            );
+
+            let (to_serial_tx, mut to_serial_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+            let (from_serial_tx, _) = broadcast::channel::<Vec<u8>>(256);
+            let mutexed_serial_port = Arc::new(Mutex::new(serial_port));
+
+            // ---------- Task: read from serial, broadcast to all clients ----------
+            let reader_task = {
+                let readable_serial_port = Arc::clone(&mutexed_serial_port);
+                let tx = from_serial_tx.clone();
+                let name = self.name.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    loop {
+                        let read_result = {
+                            let mut ser = readable_serial_port.lock().await;
+                            let ser = ser.as_mut();
+                            ser.read(&mut buf)
+                        };
+                        let n = match read_result {
+                            Ok(cnt) => cnt,
+                            Err(e) => {
+                                error!("Serial read error on `{}`: {:#}", name, e);
+                                break;
+                            }
+                        };
+                        if n == 0 {
+                            continue;
+                        }
+                        // Ignore send errors: having no subscribers is fine.
+                        let _ = tx.send(buf[..n].to_vec());
+                    }
+                })
+            };
+
+            // ---------- Task: write to serial everything clients send ----------
+            let writer_task = {
+                let writable_serial_port = Arc::clone(&mutexed_serial_port);
+                let name = self.name.clone();
+                tokio::spawn(async move {
+                    while let Some(bytes) = to_serial_rx.recv().await {
+                        let write_result = {
+                            let mut ser = writable_serial_port.lock().await;
+                            let ser = ser.as_mut();
+                            ser.write_all(&bytes)
+                        };
+                        if write_result.is_err() {
+                            error!("Serial write error on `{}`", name);
+                            break;
+                        }
+                    }
+                })
+            };
+
+            state.inner = Some(HubInner {
+                to_serial_tx,
+                from_serial_tx,
+                serial_port: mutexed_serial_port,
+                reader_task,
+                writer_task,
+            });
+        }
+
+        let inner = state.inner.as_ref().expect("hub was just opened above");
+        let client = HubClient {
+            hub: self.clone(),
+            to_serial_tx: inner.to_serial_tx.clone(),
+            from_serial_rx: inner.from_serial_tx.subscribe(),
+            released: false,
+        };
+        state.clients += 1;
+        Ok(client)
+    }
+
+    /// Flush the underlying serial port, if currently open. Used when draining
+    /// connections on shutdown so buffered writes aren't lost.
+    async fn flush(self: &Arc<Self>) -> Result<()> {
+        let state = self.state.lock().await;
+        let Some(inner) = state.inner.as_ref() else {
+            return Ok(());
+        };
+        let mut ser = inner.serial_port.lock().await;
+        ser.as_mut()
+            .flush()
+            .with_context(|| format!("Failed to flush serial port for `{}`", self.name))
+    }
+
+    /// Decrement the client count and close the serial port once the last client has left.
+    async fn release(self: &Arc<Self>) {
+        let mut state = self.state.lock().await;
+        state.clients = state.clients.saturating_sub(1);
+        if state.clients == 0 {
+            if let Some(inner) = state.inner.take() {
+                inner.reader_task.abort();
+                inner.writer_task.abort();
+                info!("Serial port `{}` closed (last client disconnected)", self.name);
+            }
+        }
+    }
+}
+
+impl Drop for HubClient {
+    fn drop(&mut self) {
+        // Safety net only: the normal path is the explicit, awaited `release()` above. If a
+        // `HubClient` is ever dropped without it (e.g. a future it lives in is cancelled),
+        // spawn a best-effort release so the hub doesn't leak a refcount forever, but callers
+        // should not rely on this for ordering.
+        if self.released {
+            return;
+        }
+        let hub = self.hub.clone();
+        tokio::spawn(async move {
+            hub.release().await;
+        });
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct TlsConfig {
+    /// Path to a PEM file containing the certificate chain.
+    cert_path: String,
+    /// Path to a PEM file containing the private key.
+    key_path: String,
+}
+
+/// Load a certificate chain and private key from PEM files and build a `rustls::ServerConfig`.
+fn load_tls_config(tls_cfg: &TlsConfig) -> Result<rustls::ServerConfig> {
+    let cert_file = std::fs::File::open(&tls_cfg.cert_path)
+        .with_context(|| format!("Failed to open TLS certificate {}", tls_cfg.cert_path))?;
+    let certs = rustls_pemfile::certs(&mut std::io::BufReader::new(cert_file))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to parse TLS certificate {}", tls_cfg.cert_path))?;
+
+    let key_file = std::fs::File::open(&tls_cfg.key_path)
+        .with_context(|| format!("Failed to open TLS private key {}", tls_cfg.key_path))?;
+    let key = rustls_pemfile::private_key(&mut std::io::BufReader::new(key_file))
+        .with_context(|| format!("Failed to parse TLS private key {}", tls_cfg.key_path))?
+        .with_context(|| format!("No private key found in {}", tls_cfg.key_path))?;
+
+    rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("Failed to build TLS server config")
+}
+
+/// Shared application state: the parsed configuration plus one `SerialHub` per endpoint.
+struct AppState {
+    cfg: Config,
+    hubs: HashMap<String, Arc<SerialHub>>,
+}
+
+/// Owns a listener's accept loop and its spawned connection tasks, and can be shut down
+/// gracefully via `close_on(trigger)`: stops accepting new connections once `trigger`
+/// completes, while letting existing connections finish on their own, only forcing them
+/// to close once `drain_timeout` has elapsed.
+struct ListenerHandle {
+    accept_cancel: CancellationToken,
+    conn_shutdown: CancellationToken,
+    tasks: Arc<Mutex<JoinSet<()>>>,
+    accept_task: JoinHandle<()>,
+    drain_timeout: Duration,
+}
+
+impl ListenerHandle {
+    /// Spawn `run_accept_loop` immediately. It is handed an `accept_cancel` token it should
+    /// stop accepting on, a `conn_shutdown` token to pass down to each connection it spawns,
+    /// and the `JoinSet` each connection task should be spawned into.
+    fn new<F, Fut>(drain_timeout: Duration, run_accept_loop: F) -> Self
+    where
+        F: FnOnce(CancellationToken, CancellationToken, Arc<Mutex<JoinSet<()>>>) -> Fut,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let accept_cancel = CancellationToken::new();
+        let conn_shutdown = CancellationToken::new();
+        let tasks = Arc::new(Mutex::new(JoinSet::new()));
+        let accept_task = tokio::spawn(run_accept_loop(
+            accept_cancel.clone(),
+            conn_shutdown.clone(),
+            tasks.clone(),
+        ));
+        Self {
+            accept_cancel,
+            conn_shutdown,
+            tasks,
+            accept_task,
+            drain_timeout,
+        }
+    }
+
+    /// Drain `self.tasks`, returning once all of them have finished.
+    async fn drain(tasks: &Arc<Mutex<JoinSet<()>>>) {
+        let mut tasks = tasks.lock().await;
+        while tasks.join_next().await.is_some() {}
+    }
+
+    /// Stop accepting new connections once `trigger` completes, then let existing
+    /// connections finish on their own. If they haven't drained within `drain_timeout`,
+    /// ask them to close (WebSocket `Close` frame, flushed serial port) and give them one
+    /// more `drain_timeout` before aborting whatever is left.
+    async fn close_on<Fut>(self, trigger: Fut)
+    where
+        Fut: std::future::Future<Output = ()>,
+    {
+        trigger.await;
+        self.accept_cancel.cancel();
+        let _ = self.accept_task.await;
+
+        if tokio::time::timeout(self.drain_timeout, Self::drain(&self.tasks)).await.is_err() {
+            info!("Drain timeout reached; asking remaining connections to close");
+            self.conn_shutdown.cancel();
+            if tokio::time::timeout(self.drain_timeout, Self::drain(&self.tasks)).await.is_err() {
+                self.tasks.lock().await.abort_all();
+            }
+        }
+    }
 }
 
 #[tokio::main]
@@ -57,45 +418,172 @@ async fn main() -> Result<()> {
     };
     info!("Configuration loaded: {} endpoints", cfg.endpoints.len());
 
-    // Shared map of endpoint -> SerialConfig (Arc for cheap cloning into tasks)
-    let cfg = Arc::new(cfg);
+    // Build a TLS acceptor up front if a `tls` section is configured.
+    let tls_acceptor = cfg
+        .tls
+        .as_ref()
+        .map(load_tls_config)
+        .transpose()?
+        .map(|server_cfg| TlsAcceptor::from(Arc::new(server_cfg)));
+
+    // One SerialHub per endpoint, so concurrent clients share a single open serial port.
+    let hubs: HashMap<String, Arc<SerialHub>> = cfg
+        .endpoints
+        .iter()
+        .map(|(name, serial_cfg)| (name.clone(), SerialHub::new(name.clone(), serial_cfg.clone())))
+        .collect();
+
+    // Shared application state (Arc for cheap cloning into tasks)
+    let state = Arc::new(AppState { cfg, hubs });
 
     // Bind a TCP listener – we’ll serve all WebSocket endpoints on the same port.
-    let addr = format!("{}:{}", cfg.bind_address, cfg.bind_port);
+    let addr = format!("{}:{}", state.cfg.bind_address, state.cfg.bind_port);
     let listener = TcpListener::bind(&addr)
         .await
         .with_context(|| format!("Failed to bind TCP listener on {}", addr))?;
-    info!("Listening for WebSocket connections on ws://{}/<endpoint>", addr);
+    info!(
+        "Listening for WebSocket connections on {}://{}/<endpoint>",
+        if tls_acceptor.is_some() { "wss" } else { "ws" },
+        addr
+    );
+
+    // Bind the Unix domain socket listener, if configured. Remove any stale socket file
+    // left behind by a previous, uncleanly terminated run before binding.
+    let unix_listener = if let Some(path) = &state.cfg.unix_socket {
+        if std::fs::metadata(path).is_ok() {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove stale Unix socket {}", path))?;
+        }
+        let listener = UnixListener::bind(path)
+            .with_context(|| format!("Failed to bind Unix socket listener on {}", path))?;
+        info!("Listening for WebSocket connections on unix:{}/<endpoint>", path);
+        Some(listener)
+    } else {
+        None
+    };
 
-    // Signal handling – when Ctrl‑C is received we break the accept loop.
-    let shutdown_signal = async {
-        signal::ctrl_c().await.expect("Failed to listen for ctrl_c");
-        info!("Ctrl‑C received, shutting down");
+    let drain_timeout = Duration::from_secs(state.cfg.shutdown_drain_timeout_secs);
+
+    // Wrap the WebSocket/TLS TCP listener in a `ListenerHandle` so it can be shut down
+    // gracefully instead of having its in-flight connections dropped abruptly.
+    let ws_handle = {
+        let state = state.clone();
+        ListenerHandle::new(drain_timeout, move |accept_cancel, conn_shutdown, tasks| {
+            accept_loop(listener, state, tls_acceptor, accept_cancel, conn_shutdown, tasks)
+        })
     };
 
-    // Accept loop (runs until shutdown signal)
-    tokio::select! {
-        _ = accept_loop(listener, cfg.clone()) => {},
-        _ = shutdown_signal => {},
+    // Wrap the Unix socket listener the same way, if configured.
+    let unix_handle = unix_listener.map(|listener| {
+        let state = state.clone();
+        ListenerHandle::new(drain_timeout, move |accept_cancel, conn_shutdown, tasks| {
+            accept_loop_unix(listener, state, accept_cancel, conn_shutdown, tasks)
+        })
+    });
+
+    // Bind a dedicated TCP listener for each `raw_tcp` mode endpoint, each with its own handle.
+    let mut raw_tcp_handles = Vec::new();
+    for (name, serial_cfg) in state.cfg.endpoints.iter() {
+        if serial_cfg.mode != ConnectionMode::RawTcp {
+            continue;
+        }
+        let port = serial_cfg
+            .raw_tcp_port
+            .with_context(|| format!("Endpoint `{}` uses raw_tcp mode but has no raw_tcp_port", name))?;
+        let addr = format!("{}:{}", state.cfg.bind_address, port);
+        let listener = TcpListener::bind(&addr)
+            .await
+            .with_context(|| format!("Failed to bind raw TCP listener on {}", addr))?;
+        info!("Listening for raw TCP connections on {} for endpoint `{}`", addr, name);
+        let hub = state.hubs.get(name).expect("hub built for every endpoint").clone();
+        let name = name.clone();
+        raw_tcp_handles.push(ListenerHandle::new(drain_timeout, move |accept_cancel, conn_shutdown, tasks| {
+            accept_loop_raw_tcp(listener, hub, name, accept_cancel, conn_shutdown, tasks)
+        }));
+    }
+
+    // Spawn an outbound client task for each configured outbound endpoint.
+    let outbound_tasks: Vec<_> = state
+        .cfg
+        .outbound
+        .iter()
+        .cloned()
+        .map(|outbound_cfg| tokio::spawn(run_outbound_client(outbound_cfg)))
+        .collect();
+
+    // Wait directly for Ctrl‑C, then close every listener gracefully: each one stops
+    // accepting immediately but gives its existing connections a chance to drain before
+    // being forced to close. Closed concurrently so total shutdown time is bounded by a
+    // single listener's close (at most twice `shutdown_drain_timeout_secs`), not stacked
+    // per listener.
+    signal::ctrl_c().await.expect("Failed to listen for ctrl_c");
+    info!("Ctrl‑C received, shutting down");
+
+    let mut close_futs: Vec<std::pin::Pin<Box<dyn std::future::Future<Output = ()>>>> =
+        vec![Box::pin(ws_handle.close_on(futures::future::ready(())))];
+    if let Some(handle) = unix_handle {
+        close_futs.push(Box::pin(handle.close_on(futures::future::ready(()))));
+    }
+    for handle in raw_tcp_handles {
+        close_futs.push(Box::pin(handle.close_on(futures::future::ready(()))));
+    }
+    futures::future::join_all(close_futs).await;
+
+    for task in outbound_tasks {
+        task.abort();
+    }
+    if let Some(path) = &state.cfg.unix_socket {
+        let _ = std::fs::remove_file(path);
     }
 
     Ok(())
 }
 
-/// Accept incoming TCP connections, upgrade them to WebSocket and hand them to `handle_connection`.
-async fn accept_loop(listener: TcpListener, cfg: Arc<Config>) {
+/// Accept incoming TCP connections, optionally terminate TLS, upgrade them to WebSocket
+/// and hand them to `handle_connection`. Stops accepting as soon as `accept_cancel` fires;
+/// each spawned connection is tracked in `tasks` and handed `conn_shutdown` so it can be
+/// asked to wrap up gracefully later.
+async fn accept_loop(
+    listener: TcpListener,
+    state: Arc<AppState>,
+    tls_acceptor: Option<TlsAcceptor>,
+    accept_cancel: CancellationToken,
+    conn_shutdown: CancellationToken,
+    tasks: Arc<Mutex<JoinSet<()>>>,
+) {
     loop {
-        match listener.accept().await {
+        let accepted = tokio::select! {
+            _ = accept_cancel.cancelled() => break,
+            accepted = listener.accept() => accepted,
+        };
+        match accepted {
             Ok((stream, _addr)) => {
-                // Clone config reference for each connection
-                let cfg = cfg.clone();
+                // Clone state reference for each connection
+                let state = state.clone();
+                let conn_shutdown = conn_shutdown.clone();
 
-                // Spawn a task to handle the connection independently
-                tokio::spawn(async move {
-                    if let Err(e) = handle_connection(stream, cfg).await {
-                        error!("Connection handling error: {:#}", e);
+                match tls_acceptor.clone() {
+                    Some(acceptor) => {
+                        tasks.lock().await.spawn(async move {
+                            match acceptor.accept(stream).await {
+                                Ok(tls_stream) => {
+                                    if let Err(e) = handle_connection(tls_stream, state, conn_shutdown).await {
+                                        error!("Connection handling error: {:#}", e);
+                                    }
+                                }
+                                Err(e) => error!("TLS handshake failed: {:#}", e),
+                            }
+                        });
                     }
-                });
+                    None => {
+                        // Spawn a task to handle the connection independently
+                        tasks.lock().await.spawn(async move {
+                            if let Err(e) = handle_connection(stream, state, conn_shutdown).await {
+                                error!("Connection handling error: {:#}", e);
+                            }
+                        });
+                    }
+                }
             }
             Err(e) => {
                 error!("Failed to accept TCP connection: {:#}", e);
@@ -106,75 +594,224 @@ async fn accept_loop(listener: TcpListener, cfg: Arc<Config>) {
     }
 }
 
-/// Upgrade a raw TCP stream to a WebSocket, parse the URL path to decide which serial
-/// port to open, then forward traffic bi‑directionally.
-async fn handle_connection(
-    raw_stream: tokio::net::TcpStream,
-    cfg: Arc<Config>,
-) -> Result<()> {
-    let mut port_cfg: Option<&SerialConfig> = None;
-    let mut ws_endpoint: String = "".to_string();
-    // Perform the WebSocket handshake – we need the request URI to know the endpoint name.
-    let ws_stream = {
-        let cb = |req: &http::Request<()>, resp: http::Response<()>| {
-        // Extract the request path (e.g. "/mydevice")
-        ws_endpoint = req.uri().path().trim_start_matches('/').to_string();
-        port_cfg = cfg
-            .endpoints
-            .get(ws_endpoint.as_str());
-        if port_cfg.is_none() {
-            return Ok(http::Response::builder().status(404).body(()).unwrap());// 404 Not Found
+/// Accept incoming connections on a Unix domain socket and hand them to `handle_connection`.
+/// Unix sockets are local-only, so TLS termination does not apply here.
+async fn accept_loop_unix(
+    listener: UnixListener,
+    state: Arc<AppState>,
+    accept_cancel: CancellationToken,
+    conn_shutdown: CancellationToken,
+    tasks: Arc<Mutex<JoinSet<()>>>,
+) {
+    loop {
+        let accepted = tokio::select! {
+            _ = accept_cancel.cancelled() => break,
+            accepted = listener.accept() => accepted,
+        };
+        match accepted {
+            Ok((stream, _addr)) => {
+                let state = state.clone();
+                let conn_shutdown = conn_shutdown.clone();
+                tasks.lock().await.spawn(async move {
+                    if let Err(e) = handle_connection(stream, state, conn_shutdown).await {
+                        error!("Connection handling error: {:#}", e);
+                    }
+                });
+            }
+            Err(e) => {
+                error!("Failed to accept Unix socket connection: {:#}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
         }
-        return Ok(resp);
+    }
+}
+
+/// Accept incoming connections for a `raw_tcp` mode endpoint and hand them to
+/// `handle_raw_tcp_connection`. There is no WebSocket handshake in this mode.
+async fn accept_loop_raw_tcp(
+    listener: TcpListener,
+    hub: Arc<SerialHub>,
+    endpoint: String,
+    accept_cancel: CancellationToken,
+    conn_shutdown: CancellationToken,
+    tasks: Arc<Mutex<JoinSet<()>>>,
+) {
+    loop {
+        let accepted = tokio::select! {
+            _ = accept_cancel.cancelled() => break,
+            accepted = listener.accept() => accepted,
+        };
+        match accepted {
+            Ok((stream, _addr)) => {
+                let hub = hub.clone();
+                let endpoint = endpoint.clone();
+                let conn_shutdown = conn_shutdown.clone();
+                tasks.lock().await.spawn(async move {
+                    if let Err(e) = handle_raw_tcp_connection(stream, hub, &endpoint, conn_shutdown).await {
+                        error!("Raw TCP connection handling error: {:#}", e);
+                    }
+                });
+            }
+            Err(e) => {
+                error!("Failed to accept raw TCP connection: {:#}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+}
+
+/// Bridge a raw TCP socket to a serial port via the endpoint's `SerialHub`, with no
+/// WebSocket framing: bytes read from serial are written straight to the socket and
+/// vice versa.
+async fn handle_raw_tcp_connection(
+    socket: tokio::net::TcpStream,
+    hub: Arc<SerialHub>,
+    endpoint: &str,
+    conn_shutdown: CancellationToken,
+) -> Result<()> {
+    let client = hub.subscribe().await?;
+    let to_serial_tx = client.to_serial_tx.clone();
+    let (mut socket_rx, mut socket_tx) = tokio::io::split(socket);
+
+    // ---------- Task: forward broadcasted serial data straight to the socket ----------
+    // Hands the hub client back when it finishes, so the caller can release it explicitly
+    // instead of relying on `Drop`. Stops on `conn_shutdown` or on `stop_forwarder`, raised
+    // once the read loop below ends on its own.
+    let stop_forwarder = CancellationToken::new();
+    let broadcast_forwarder: JoinHandle<HubClient> = {
+        let conn_shutdown = conn_shutdown.clone();
+        let stop_forwarder = stop_forwarder.clone();
+        let endpoint = endpoint.to_string();
+        tokio::spawn(async move {
+            let mut client = client;
+            loop {
+                tokio::select! {
+                    _ = conn_shutdown.cancelled() => break,
+                    _ = stop_forwarder.cancelled() => break,
+                    recv = client.from_serial_rx.recv() => match recv {
+                        Ok(bytes) => {
+                            if socket_tx.write_all(&bytes).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Raw TCP client for `{}` lagged, dropped {} serial message(s)", endpoint, n);
+                            continue;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                }
+            }
+            client
+        })
     };
-        tokio_tungstenite::accept_hdr_async(raw_stream, cb)
-    .await
-    .context("WebSocket handshake failed")?};
 
-    // Look up the serial configuration for this endpoint.
-    let serial_cfg = port_cfg.unwrap()
-        .clone();
+    // ---------- Read from the socket, forward straight to the hub's serial writer ----------
+    let mut buf = [0u8; 1024];
+    loop {
+        tokio::select! {
+            _ = conn_shutdown.cancelled() => {
+                if let Err(e) = hub.flush().await {
+                    error!("Failed to flush serial port for `{}`: {:#}", endpoint, e);
+                }
+                break;
+            }
+            read = socket_rx.read(&mut buf) => {
+                let n = match read {
+                    Ok(0) => break, // client closed the connection
+                    Ok(n) => n,
+                    Err(e) => {
+                        error!("Raw TCP read error: {:#}", e);
+                        break;
+                    }
+                };
+                if to_serial_tx.send(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        }
+    }
 
-    // Open the serial port (blocking call – run in a dedicated thread via spawn_blocking).
-    let serial_port = tokio::task::spawn_blocking(move || {
-        serialport::new(&serial_cfg.port, serial_cfg.baud_rate)
-            .timeout(Duration::from_secs(10))
-            .open()
-            .with_context(|| format!("Failed to open serial port {}", &serial_cfg.port))
-    })
-    .await??; // Propagate any errors from the blocking task.
+    // Tell the forwarder to stop (it may already have, via `conn_shutdown`), get the hub
+    // client back, and release it explicitly so the hub's refcount decrement has happened
+    // before we return.
+    stop_forwarder.cancel();
+    if let Ok(client) = broadcast_forwarder.await {
+        client.release().await;
+    }
+    info!("Raw TCP connection for endpoint `{}` terminated", endpoint);
+    Ok(())
+}
+
+/// Dial out to a remote WebSocket server and bridge it to a local serial port, reconnecting
+/// with exponential backoff whenever the remote drops or cannot be reached.
+async fn run_outbound_client(cfg: OutboundConfig) {
+    const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+    const MAX_BACKOFF: Duration = Duration::from_secs(60);
+    // A connection has to stay up at least this long before we consider it "established"
+    // and reset the backoff; otherwise a remote that accepts the connection but breaks
+    // the bridge immediately (e.g. the local serial port won't open) would reset backoff
+    // to 1s on every iteration and hammer the remote in a tight loop.
+    const STABLE_CONNECTION_THRESHOLD: Duration = Duration::from_secs(30);
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        match tokio_tungstenite::connect_async(&cfg.url).await {
+            Ok((ws_stream, _response)) => {
+                info!("Outbound `{}` connected to {}", cfg.name, cfg.url);
+                let connected_at = std::time::Instant::now();
+                if let Err(e) = bridge_outbound(ws_stream, &cfg).await {
+                    error!("Outbound `{}` bridge error: {:#}", cfg.name, e);
+                }
+                info!("Outbound `{}` disconnected, reconnecting", cfg.name);
+                backoff = if connected_at.elapsed() >= STABLE_CONNECTION_THRESHOLD {
+                    INITIAL_BACKOFF
+                } else {
+                    (backoff * 2).min(MAX_BACKOFF)
+                };
+            }
+            Err(e) => {
+                error!("Outbound `{}` failed to connect to {}: {:#}", cfg.name, cfg.url, e);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+        tokio::time::sleep(backoff).await;
+    }
+}
 
+/// Open the serial port for an outbound endpoint and forward traffic between it and an
+/// already-established outbound WebSocket connection, reusing the same blocking serial
+/// reader/writer task pattern as `SerialHub`.
+async fn bridge_outbound<S>(
+    ws_stream: tokio_tungstenite::WebSocketStream<S>,
+    cfg: &OutboundConfig,
+) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let serial_port = open_serial_port(&cfg.port, cfg.baud_rate).await?;
     info!(
-        "Serial port `{}` opened at {} baud for endpoint `{}`",
-        &port_cfg.unwrap().port, serial_cfg.baud_rate, ws_endpoint
+        "Serial port `{}` opened at {} baud for outbound endpoint `{}`",
+        cfg.port, cfg.baud_rate, cfg.name
     );
 
-    // Split the WebSocket into a sender and receiver.
     let (mut ws_tx, mut ws_rx) = ws_stream.split();
-
-    // Channel used to forward data read from the serial port to the WebSocket task.
     let (serial_to_ws_tx, mut serial_to_ws_rx) = mpsc::unbounded_channel::<Message>();
-
-    // Wrap the serial port in an Arc<Mutex<>> so both tasks can use it.
     let mutexed_serial_port = Arc::new(Mutex::new(serial_port));
 
-    // ---------- Task: read from serial, send to WebSocket ----------
+    // ---------- Task: read from serial, send to the WebSocket ----------
     let serial_reader = {
         let readable_serial_port = Arc::clone(&mutexed_serial_port);
         let tx = serial_to_ws_tx.clone();
         tokio::spawn(async move {
-            // We use a small buffer and block on the read in a blocking thread.
             let mut buf = [0u8; 1024];
-            let readable_serial_port = readable_serial_port;
             loop {
-                // Read from serial in a blocking fashion.
-                let read_result= {
+                let read_result = {
                     let mut ser = readable_serial_port.lock().await;
                     let ser = ser.as_mut();
                     ser.read(&mut buf)
                 };
-                let n = match read_result
-                {
+                let n = match read_result {
                     Ok(cnt) => cnt,
                     Err(e) => {
                         error!("Serial read error: {:#}", e);
@@ -182,12 +819,9 @@ async fn handle_connection(
                     }
                 };
                 if n == 0 {
-                    // EOF (should not normally happen on serial ports)
                     continue;
                 }
-                // Forward the bytes as a binary WebSocket message.
                 if tx.send(Message::Binary(buf[..n].to_vec())).is_err() {
-                    // Receiver has been dropped – connection closed.
                     break;
                 }
             }
@@ -195,9 +829,9 @@ async fn handle_connection(
     };
 
     // ---------- Task: write to serial from incoming WebSocket messages ----------
-    let serial_writer = {
+    let mut serial_writer = {
         let writable_serial_port = Arc::clone(&mutexed_serial_port);
-        let writer_endpoint = ws_endpoint.clone();
+        let name = cfg.name.clone();
         tokio::spawn(async move {
             while let Some(msg) = ws_rx.next().await {
                 match msg {
@@ -205,38 +839,32 @@ async fn handle_connection(
                         let write_result = {
                             let mut ser = writable_serial_port.lock().await;
                             let ser = ser.as_mut();
-                            let data = bytes.clone();
-                            ser.write_all(&data)
+                            ser.write_all(&bytes)
                         };
                         if write_result.is_err() {
-                            // Error writing to serial port.
                             break;
                         }
                     }
                     Ok(Message::Text(text)) => {
-                        // Convert text messages to bytes if needed.
                         let bytes = text.into_bytes();
-                        // Write to serial (blocking)
                         let write_result = {
                             let mut ser = writable_serial_port.lock().await;
                             let ser = ser.as_mut();
-                            let data = bytes.clone();
-                            ser.write_all(&data)
+                            ser.write_all(&bytes)
                         };
                         if write_result.is_err() {
-                            // Error writing to serial port.
                             break;
                         }
                     }
                     Ok(Message::Close(_)) => {
-                        info!("WebSocket client closed connection for `{}`", writer_endpoint);
+                        info!("Remote server closed outbound connection `{}`", name);
                         break;
                     }
                     Ok(_) => {
                         // Ping/Pong/etc. are ignored.
                     }
                     Err(e) => {
-                        error!("WebSocket receive error: {:#}", e);
+                        error!("Outbound WebSocket receive error: {:#}", e);
                         break;
                     }
                 }
@@ -245,16 +873,150 @@ async fn handle_connection(
     };
 
     // ---------- Forward data from serial_to_ws_rx to the WebSocket ----------
-    while let Some(msg) = serial_to_ws_rx.recv().await {
-        if let Err(e) = ws_tx.send(msg).await {
-            error!("Failed to send data to WebSocket: {:#}", e);
-            break;
+    // Also stop as soon as the receiving task ends, so a remote close is noticed even while
+    // the serial port is quiet.
+    loop {
+        tokio::select! {
+            _ = &mut serial_writer => break,
+            msg = serial_to_ws_rx.recv() => {
+                let Some(msg) = msg else { break };
+                if let Err(e) = ws_tx.send(msg).await {
+                    error!("Failed to send data to outbound WebSocket: {:#}", e);
+                    break;
+                }
+            }
         }
     }
 
-    // When the forwarding loop ends, make sure the background tasks are shut down.
     serial_reader.abort();
     serial_writer.abort();
+    Ok(())
+}
+
+/// Upgrade a raw stream (plain TCP or TLS) to a WebSocket, parse the URL path to decide
+/// which serial endpoint to subscribe to, then forward traffic bi‑directionally via that
+/// endpoint's `SerialHub`.
+async fn handle_connection<S>(raw_stream: S, state: Arc<AppState>, conn_shutdown: CancellationToken) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let mut endpoint_known = false;
+    let mut ws_endpoint: String = "".to_string();
+    // Perform the WebSocket handshake – we need the request URI to know the endpoint name.
+    let ws_stream = {
+        let cb = |req: &http::Request<()>, resp: http::Response<()>| {
+        // Extract the request path (e.g. "/mydevice")
+        ws_endpoint = req.uri().path().trim_start_matches('/').to_string();
+        // Only endpoints still in `websocket` mode are reachable here; `raw_tcp` endpoints
+        // are exclusively exposed on their dedicated `raw_tcp_port`.
+        endpoint_known = state
+            .cfg
+            .endpoints
+            .get(ws_endpoint.as_str())
+            .is_some_and(|serial_cfg| serial_cfg.mode == ConnectionMode::WebSocket);
+        if !endpoint_known {
+            return Ok(http::Response::builder().status(404).body(()).unwrap());// 404 Not Found
+        }
+        return Ok(resp);
+    };
+        tokio_tungstenite::accept_hdr_async(raw_stream, cb)
+    .await
+    .context("WebSocket handshake failed")?};
+
+    // Subscribe to the endpoint's serial hub, opening the port if we're the first client.
+    let hub = state
+        .hubs
+        .get(&ws_endpoint)
+        .with_context(|| format!("No serial hub for endpoint `{}`", ws_endpoint))?
+        .clone();
+    let client = hub.subscribe().await?;
+    let to_serial_tx = client.to_serial_tx.clone();
+
+    // Split the WebSocket into a sender and receiver.
+    let (mut ws_tx, mut ws_rx) = ws_stream.split();
+
+    // ---------- Task: forward broadcasted serial data to the WebSocket ----------
+    // Hands the hub client back when it finishes, so the caller can release it explicitly
+    // instead of relying on `Drop`. Stops on `conn_shutdown` (sending a WebSocket `Close`
+    // frame first) or on `stop_reader`, raised once the writer loop below ends on its own.
+    let stop_reader = CancellationToken::new();
+    let serial_reader: JoinHandle<HubClient> = {
+        let mut client = client;
+        let conn_shutdown = conn_shutdown.clone();
+        let stop_reader = stop_reader.clone();
+        let endpoint = ws_endpoint.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = conn_shutdown.cancelled() => {
+                        let _ = ws_tx.send(Message::Close(None)).await;
+                        break;
+                    }
+                    _ = stop_reader.cancelled() => break,
+                    recv = client.from_serial_rx.recv() => match recv {
+                        Ok(bytes) => {
+                            if ws_tx.send(Message::Binary(bytes)).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("WebSocket client for `{}` lagged, dropped {} serial message(s)", endpoint, n);
+                            continue;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                }
+            }
+            client
+        })
+    };
+
+    // ---------- Write to the hub's serial writer from incoming WebSocket messages ----------
+    let writer_endpoint = ws_endpoint.clone();
+    loop {
+        tokio::select! {
+            _ = conn_shutdown.cancelled() => {
+                if let Err(e) = hub.flush().await {
+                    error!("Failed to flush serial port for `{}`: {:#}", writer_endpoint, e);
+                }
+                break;
+            }
+            msg = ws_rx.next() => {
+                match msg {
+                    Some(Ok(Message::Binary(bytes))) => {
+                        if to_serial_tx.send(bytes.to_vec()).is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Text(text))) => {
+                        if to_serial_tx.send(text.into_bytes()).is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) => {
+                        info!("WebSocket client closed connection for `{}`", writer_endpoint);
+                        break;
+                    }
+                    Some(Ok(_)) => {
+                        // Ping/Pong/etc. are ignored.
+                    }
+                    Some(Err(e)) => {
+                        error!("WebSocket receive error: {:#}", e);
+                        break;
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    // Tell the reader task to stop (it may already have, via `conn_shutdown`), get the hub
+    // client back, and release it explicitly so the hub's refcount decrement has happened
+    // before we return.
+    stop_reader.cancel();
+    if let Ok(client) = serial_reader.await {
+        client.release().await;
+    }
 
     info!("Connection for endpoint `{}` terminated", ws_endpoint);
     Ok(())